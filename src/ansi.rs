@@ -0,0 +1,31 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Matches a single ANSI CSI escape sequence (e.g. `\x1b[1;34m`), the kind terminal programs
+/// emit for color and cursor control and that shows up verbatim in captured pane output.
+fn ansi_escape() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").unwrap())
+}
+
+/// Strips ANSI CSI escape sequences (those [`ansi_escape`] matches) from `input`, so they are
+/// not matched and styled as if they were content. Other escape sequences, such as OSC
+/// (`\x1b]...`) or CSI sequences with `?` in their parameters, are left in place.
+pub(crate) fn strip_ansi(input: &str) -> String {
+    ansi_escape().replace_all(input, "").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_color_sequences() {
+        assert_eq!(strip_ansi("\x1b[1;34mHi @buddy\x1b[0m"), "Hi @buddy");
+    }
+
+    #[test]
+    fn leaves_plain_text_untouched() {
+        assert_eq!(strip_ansi("Hi @buddy"), "Hi @buddy");
+    }
+}
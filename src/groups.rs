@@ -0,0 +1,169 @@
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use regex::Captures;
+
+use crate::{safe_slice, IntoRegexRef};
+
+/// Identifies a capture group within a match, either by its index or by its name.
+///
+/// Implemented for `usize` (index) and `&str` (name), so the group-to-style mapping passed to
+/// [`highlight_line_groups`] can identify groups either way; all entries of one mapping use
+/// the same kind of identifier.
+pub trait CaptureGroupId {
+    fn resolve<'t>(&self, captures: &Captures<'t>) -> Option<regex::Match<'t>>;
+}
+
+impl CaptureGroupId for usize {
+    fn resolve<'t>(&self, captures: &Captures<'t>) -> Option<regex::Match<'t>> {
+        captures.get(*self)
+    }
+}
+
+impl CaptureGroupId for &str {
+    fn resolve<'t>(&self, captures: &Captures<'t>) -> Option<regex::Match<'t>> {
+        captures.name(self)
+    }
+}
+
+/// Creates a `Line` from the given `line` argument, styling only the spans covered by the
+/// requested capture groups and leaving the rest of each match unstyled.
+///
+/// # Arguments
+///
+/// * `line` - A string that holds the line of text to be highlighted.
+/// * `pattern` - A regular expression pattern, with capture groups, to match against `line`.
+/// * `group_styles` - A mapping from capture group (index or name) to the `Style` applied to
+///   its span. Groups that did not participate in a given match are skipped.
+///
+/// # Example
+///
+/// ```
+/// use tui_pattern_highlighter::highlight_line_groups;
+/// use ratatui::{
+///     style::{Color, Style},
+///     text::{Line, Span},
+/// };
+///
+/// let line = "[label](https://example.com)";
+/// let pattern = r"\[([^]]*)\]\(([^)]+)\)";
+/// let label_style = Style::new().fg(Color::Yellow);
+/// let url_style = Style::new().fg(Color::Blue);
+///
+/// let expected_line = Line::from(vec![
+///     Span::from("["),
+///     Span::from("label").style(label_style),
+///     Span::from("]("),
+///     Span::from("https://example.com").style(url_style),
+///     Span::from(")"),
+/// ]);
+///
+/// assert_eq!(
+///     highlight_line_groups(line, pattern, [(1, label_style), (2, url_style)]),
+///     expected_line
+/// );
+/// ```
+///
+/// # Panics
+///
+/// The function may panic if the provided pattern is an invalid regular expression.
+pub fn highlight_line_groups<'a, S, T, G>(
+    line: S,
+    pattern: T,
+    group_styles: impl IntoIterator<Item = (G, Style)>,
+) -> Line<'a>
+where
+    S: Into<String>,
+    T: IntoRegexRef,
+    G: CaptureGroupId,
+{
+    let line_string = line.into();
+    let regex_ref = pattern.into_regex_ref();
+    let group_styles: Vec<(G, Style)> = group_styles.into_iter().collect();
+
+    let mut spans: Vec<(usize, usize, Style)> = Vec::new();
+
+    for captures in regex_ref.captures_iter(&line_string) {
+        for (group, style) in &group_styles {
+            if let Some(m) = group.resolve(&captures) {
+                spans.push((m.start(), m.end(), *style));
+            }
+        }
+    }
+
+    spans.sort_by_key(|(start, _, _)| *start);
+
+    let mut highlighted_line = Line::default();
+    let mut last_index = 0;
+
+    for (start, end, style) in spans {
+        if start < last_index {
+            continue;
+        }
+        if start > last_index {
+            highlighted_line.push_span(Span::from(safe_slice(&line_string, last_index..start).to_string()));
+        }
+        highlighted_line.push_span(Span::from(safe_slice(&line_string, start..end).to_string()).style(style));
+        last_index = end;
+    }
+
+    if line_string.len() > last_index {
+        highlighted_line.push_span(Span::from(safe_slice(&line_string, last_index..line_string.len()).to_string()));
+    }
+
+    highlighted_line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn styles_only_requested_groups() {
+        let line = "[label](https://example.com)";
+        let pattern = r"\[([^]]*)\]\(([^)]+)\)";
+        let label_style = Style::new().fg(Color::Yellow);
+        let url_style = Style::new().fg(Color::Blue);
+
+        let returned_line = highlight_line_groups(line, pattern, [(1, label_style), (2, url_style)]);
+
+        let expected_line = Line::from(vec![
+            Span::from("["),
+            Span::from("label").style(label_style),
+            Span::from("]("),
+            Span::from("https://example.com").style(url_style),
+            Span::from(")"),
+        ]);
+
+        assert_eq!(returned_line, expected_line);
+    }
+
+    #[test]
+    fn skips_groups_that_did_not_participate() {
+        let line = "foo";
+        let pattern = r"foo(bar)?";
+        let style = Style::new().fg(Color::Red);
+
+        let returned_line = highlight_line_groups(line, pattern, [(1, style)]);
+
+        assert_eq!(returned_line, Line::from(vec![Span::from("foo")]));
+    }
+
+    #[test]
+    fn styles_named_groups() {
+        let line = "Hi @buddy";
+        let pattern = r"@(?P<name>\w+)";
+        let style = Style::new().fg(Color::Green);
+
+        let returned_line = highlight_line_groups(line, pattern, [("name", style)]);
+
+        let expected_line = Line::from(vec![
+            Span::from("Hi @"),
+            Span::from("buddy").style(style),
+        ]);
+
+        assert_eq!(returned_line, expected_line);
+    }
+}
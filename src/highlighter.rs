@@ -1,25 +1,377 @@
 use ratatui::{
     style::Style,
-    text::{Line, Span},
+    text::{Line, Span, Text},
 };
 use regex::Regex;
+use std::ops::Range;
 
-pub fn highlight_line<'a>(line: &'a str, pattern: &str, highlight_style: Style) -> Line<'a> {
-    let reg = Regex::new(pattern).unwrap();
+use crate::ansi::strip_ansi;
+use crate::{own_spans, own_text, safe_slice};
 
-    let mut highlighted_line = Line::default();
+/// Holds an ordered list of `(Regex, Style)` rules and applies all of them to a line or a
+/// block of text in a single pass, instead of calling [`highlight_line`](crate::highlight_line)
+/// once per pattern and losing earlier styling.
+///
+/// When two rules match overlapping byte ranges, matches are resolved left to right: the
+/// match that starts first claims its range, ties are broken by rule order (the rule added
+/// first wins), and any later match that overlaps an already-claimed range is skipped.
+///
+/// # Example
+///
+/// ```
+/// use tui_pattern_highlighter::Highlighter;
+/// use ratatui::{
+///     style::{Color, Style},
+///     text::{Line, Span},
+/// };
+/// use regex::Regex;
+///
+/// let highlighter = Highlighter::from_rules(vec![
+///     (Regex::new(r"@\w+").unwrap(), Style::new().bg(Color::Blue)),
+///     (Regex::new(r"#\w+").unwrap(), Style::new().bg(Color::Green)),
+/// ]);
+///
+/// let line = highlighter.highlight_line("Hi @buddy #rust");
+///
+/// assert_eq!(
+///     line,
+///     Line::from(vec![
+///         Span::from("Hi "),
+///         Span::from("@buddy").style(Style::new().bg(Color::Blue)),
+///         Span::from(" "),
+///         Span::from("#rust").style(Style::new().bg(Color::Green)),
+///     ])
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Highlighter {
+    rules: Vec<(Regex, Style)>,
+    strip_ansi: bool,
+}
+
+impl Highlighter {
+    /// Compiles `pattern` once and creates a `Highlighter` with a single `(Regex, Style)`
+    /// rule, instead of recompiling the pattern on every `highlight_line`/`highlight_text`
+    /// call as the free functions do.
+    ///
+    /// # Errors
+    ///
+    /// Returns `regex::Error` if `pattern` is not a valid regular expression, instead of
+    /// panicking.
+    pub fn new(pattern: &str, style: Style) -> Result<Self, regex::Error> {
+        Ok(Self {
+            rules: vec![(Regex::new(pattern)?, style)],
+            strip_ansi: false,
+        })
+    }
+
+    /// Creates a `Highlighter` from an already-compiled, ordered list of `(Regex, Style)`
+    /// rules.
+    pub fn from_rules(rules: Vec<(Regex, Style)>) -> Self {
+        Self {
+            rules,
+            strip_ansi: false,
+        }
+    }
+
+    /// Compiles `pattern` and appends it as another rule, after the ones already held by
+    /// this `Highlighter` (and so with lower precedence than all of them).
+    ///
+    /// # Errors
+    ///
+    /// Returns `regex::Error` if `pattern` is not a valid regular expression.
+    pub fn add_rule(mut self, pattern: &str, style: Style) -> Result<Self, regex::Error> {
+        self.rules.push((Regex::new(pattern)?, style));
+        Ok(self)
+    }
+
+    /// Strips ANSI escape sequences (e.g. `\x1b[1;34m`) from the input before matching, so
+    /// captured terminal output can be highlighted without the raw escape bytes being
+    /// matched and styled as if they were content. Spans are produced aligned to the
+    /// cleaned text rather than the original input.
+    pub fn strip_ansi(mut self) -> Self {
+        self.strip_ansi = true;
+        self
+    }
+
+    /// Highlights a single line, applying every rule in one traversal.
+    pub fn highlight_line<'a>(&self, line: &'a str) -> Line<'a> {
+        if self.strip_ansi {
+            let cleaned = strip_ansi(line);
+            return own_spans(spans_from_claims(&cleaned, &self.claims(&cleaned)));
+        }
+
+        spans_from_claims(line, &self.claims(line))
+    }
+
+    /// Highlights a block of text, producing one `Line` per `'\n'`-separated line.
+    ///
+    /// Unlike splitting the text on `'\n'` first and highlighting each line in isolation,
+    /// rules are matched against the whole text in a single pass, so an `(?s)`-flagged
+    /// pattern can match across line boundaries; a match spanning several lines is broken
+    /// into one styled span per line it touches.
+    pub fn highlight_text<'a>(&self, text: &'a str) -> Text<'a> {
+        if self.strip_ansi {
+            let cleaned = strip_ansi(text);
+            return own_text(text_from_claims(&cleaned, &self.claims(&cleaned)));
+        }
+
+        text_from_claims(text, &self.claims(text))
+    }
+
+    /// Collects every match from every rule, sorted by start offset (ties broken by rule
+    /// order), then walks them left to right, discarding matches that overlap a range an
+    /// earlier match already claimed.
+    fn claims(&self, line: &str) -> Vec<(Range<usize>, Style)> {
+        let mut matches: Vec<(Range<usize>, Style, usize)> = self
+            .rules
+            .iter()
+            .enumerate()
+            .flat_map(|(rule_index, (regex, style))| {
+                regex
+                    .find_iter(line)
+                    .map(move |m| (m.range(), *style, rule_index))
+            })
+            .collect();
+
+        matches.sort_by(|a, b| a.0.start.cmp(&b.0.start).then(a.2.cmp(&b.2)));
+
+        let mut claims: Vec<(Range<usize>, Style)> = Vec::new();
+        let mut claimed_until = 0;
+
+        for (range, style, _) in matches {
+            if range.start < claimed_until {
+                continue;
+            }
+            claimed_until = range.end;
+            claims.push((range, style));
+        }
 
+        claims
+    }
+}
+
+fn spans_from_claims<'a>(line: &'a str, claims: &[(Range<usize>, Style)]) -> Line<'a> {
+    let mut highlighted_line = Line::default();
     let mut last_index = 0;
 
-    for m in reg.find_iter(&line) {
-        highlighted_line.push_span(Span::from(&line[last_index..m.start()]).style(highlight_style));
-        highlighted_line.push_span(Span::from(m.as_str()).style(highlight_style));
-        last_index = m.end() + 1;
+    for (range, style) in claims {
+        if range.start > last_index {
+            highlighted_line.push_span(Span::from(safe_slice(line, last_index..range.start)));
+        }
+        highlighted_line.push_span(Span::from(safe_slice(line, range.start..range.end)).style(*style));
+        last_index = range.end;
     }
 
     if line.len() > last_index {
-        highlighted_line.push_span(Span::from(&line[last_index..]).style(highlight_style));
+        highlighted_line.push_span(Span::from(safe_slice(line, last_index..line.len())));
     }
 
     highlighted_line
 }
+
+/// Turns whole-text `(byte_range, Style)` claims into a `Text`, breaking any claim (or gap)
+/// that spans a `'\n'` into one span per line it touches.
+fn text_from_claims<'a>(text: &'a str, claims: &[(Range<usize>, Style)]) -> Text<'a> {
+    let mut segments: Vec<(Range<usize>, Option<Style>)> = Vec::new();
+    let mut last_index = 0;
+
+    for (range, style) in claims {
+        if range.start > last_index {
+            segments.push((last_index..range.start, None));
+        }
+        segments.push((range.clone(), Some(*style)));
+        last_index = range.end;
+    }
+
+    if text.len() > last_index {
+        segments.push((last_index..text.len(), None));
+    }
+
+    let mut highlighted_text = Text::default();
+    let mut current_line = Line::default();
+    let mut after_last_newline = 0;
+
+    for (range, style) in segments {
+        let mut start = range.start;
+
+        for (rel_index, _) in safe_slice(text, range.start..range.end).match_indices('\n') {
+            let newline_index = range.start + rel_index;
+            if newline_index > start {
+                push_span(&mut current_line, safe_slice(text, start..newline_index), style);
+            }
+            highlighted_text.push_line(std::mem::take(&mut current_line));
+            start = newline_index + 1;
+            after_last_newline = start;
+        }
+
+        if start < range.end {
+            push_span(&mut current_line, safe_slice(text, start..range.end), style);
+        }
+    }
+
+    if text.len() > after_last_newline {
+        highlighted_text.push_line(current_line);
+    }
+
+    highlighted_text
+}
+
+fn push_span<'a>(line: &mut Line<'a>, content: &'a str, style: Option<Style>) {
+    line.push_span(match style {
+        Some(style) => Span::from(content).style(style),
+        None => Span::from(content),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn new_compiles_a_single_rule() {
+        let highlighter = Highlighter::new(r"@\w+", Style::new().bg(Color::Blue)).unwrap();
+
+        let line = highlighter.highlight_line("Hi @buddy");
+
+        assert_eq!(
+            line,
+            Line::from(vec![
+                Span::from("Hi "),
+                Span::from("@buddy").style(Style::new().bg(Color::Blue)),
+            ])
+        );
+    }
+
+    #[test]
+    fn new_reports_invalid_patterns() {
+        assert!(Highlighter::new(r"(", Style::default()).is_err());
+    }
+
+    #[test]
+    fn add_rule_appends_with_lower_precedence() {
+        let highlighter = Highlighter::new(r"foo\w*", Style::new().bg(Color::Blue))
+            .unwrap()
+            .add_rule(r"\w*bar", Style::new().bg(Color::Green))
+            .unwrap();
+
+        let line = highlighter.highlight_line("foobar");
+
+        assert_eq!(
+            line,
+            Line::from(vec![Span::from("foobar").style(Style::new().bg(Color::Blue))])
+        );
+    }
+
+    #[test]
+    fn applies_every_rule_in_one_pass() {
+        let highlighter = Highlighter::from_rules(vec![
+            (Regex::new(r"@\w+").unwrap(), Style::new().bg(Color::Blue)),
+            (Regex::new(r"#\w+").unwrap(), Style::new().bg(Color::Green)),
+        ]);
+
+        let line = highlighter.highlight_line("Hi @buddy #rust");
+
+        assert_eq!(
+            line,
+            Line::from(vec![
+                Span::from("Hi "),
+                Span::from("@buddy").style(Style::new().bg(Color::Blue)),
+                Span::from(" "),
+                Span::from("#rust").style(Style::new().bg(Color::Green)),
+            ])
+        );
+    }
+
+    #[test]
+    fn earlier_rule_wins_on_overlap() {
+        let highlighter = Highlighter::from_rules(vec![
+            (Regex::new(r"foo\w*").unwrap(), Style::new().bg(Color::Blue)),
+            (Regex::new(r"\w*bar").unwrap(), Style::new().bg(Color::Green)),
+        ]);
+
+        let line = highlighter.highlight_line("foobar");
+
+        assert_eq!(
+            line,
+            Line::from(vec![Span::from("foobar").style(Style::new().bg(Color::Blue))])
+        );
+    }
+
+    #[test]
+    fn highlighting_text_splits_on_newlines() {
+        let highlighter =
+            Highlighter::from_rules(vec![(Regex::new(r"@\w+").unwrap(), Style::new().bg(Color::Blue))]);
+
+        let text = highlighter.highlight_text("Hi @buddy\n@stranger hello");
+
+        assert_eq!(
+            text,
+            Text::from(vec![
+                Line::from(vec![
+                    Span::from("Hi "),
+                    Span::from("@buddy").style(Style::new().bg(Color::Blue)),
+                ]),
+                Line::from(vec![
+                    Span::from("@stranger").style(Style::new().bg(Color::Blue)),
+                    Span::from(" hello"),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn a_match_can_span_multiple_lines() {
+        let highlighter = Highlighter::from_rules(vec![(
+            Regex::new(r"(?s)```.*?```").unwrap(),
+            Style::new().bg(Color::Blue),
+        )]);
+
+        let text = highlighter.highlight_text("before\n```\ncode\n```\nafter");
+
+        assert_eq!(
+            text,
+            Text::from(vec![
+                Line::from(vec![Span::from("before")]),
+                Line::from(vec![Span::from("```").style(Style::new().bg(Color::Blue))]),
+                Line::from(vec![Span::from("code").style(Style::new().bg(Color::Blue))]),
+                Line::from(vec![Span::from("```").style(Style::new().bg(Color::Blue))]),
+                Line::from(vec![Span::from("after")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn no_trailing_empty_line_when_text_ends_with_newline() {
+        let highlighter =
+            Highlighter::from_rules(vec![(Regex::new(r"@\w+").unwrap(), Style::new().bg(Color::Blue))]);
+
+        let text = highlighter.highlight_text("Hi @buddy\n");
+
+        assert_eq!(
+            text,
+            Text::from(vec![Line::from(vec![
+                Span::from("Hi "),
+                Span::from("@buddy").style(Style::new().bg(Color::Blue)),
+            ])])
+        );
+    }
+
+    #[test]
+    fn strip_ansi_cleans_input_before_matching() {
+        let highlighter = Highlighter::new(r"@\w+", Style::new().bg(Color::Blue))
+            .unwrap()
+            .strip_ansi();
+
+        let line = highlighter.highlight_line("\x1b[1;34mHi @buddy\x1b[0m");
+
+        assert_eq!(
+            line,
+            Line::from(vec![
+                Span::from("Hi "),
+                Span::from("@buddy").style(Style::new().bg(Color::Blue)),
+            ])
+        );
+    }
+}
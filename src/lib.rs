@@ -4,6 +4,23 @@ use ratatui::{
 };
 use regex::Regex;
 use std::borrow::Cow;
+use std::ops::Range;
+
+mod ansi;
+mod groups;
+mod highlighter;
+mod rainbow;
+
+pub use groups::{highlight_line_groups, CaptureGroupId};
+pub use highlighter::Highlighter;
+pub use rainbow::{highlight_line_rainbow, hsl_to_rgb};
+
+/// Slices `s` at `range`, falling back to an empty string instead of panicking if `range`
+/// does not land on char boundaries (which a hand-rolled or byte-oriented pattern could
+/// produce against arbitrary input).
+pub(crate) fn safe_slice(s: &str, range: Range<usize>) -> &str {
+    s.get(range).unwrap_or_default()
+}
 
 /// Creates a `Line` from the given `line` argument and adds `highlight_style` to `Spans` that match the pattern.
 ///
@@ -43,29 +60,30 @@ where
     T: IntoRegexRef,
 {
     let line_string = line.into();
-    let mut highlighted_line = Line::default();
-
-    let regex_ref = pattern.into_regex_ref();
-    let mut last_index = 0;
-
-    for m in regex_ref.find_iter(&line_string).collect::<Vec<_>>() {
-        if m.start() > last_index {
-            highlighted_line.push_span(Span::from(line_string[last_index..m.start()].to_string()));
-        }
-        highlighted_line.push_span(Span::from(m.as_str().to_string()).style(highlight_style));
-        last_index = m.end();
-    }
+    let highlighter = Highlighter::from_rules(vec![(pattern.into_regex_ref().into_owned(), highlight_style)]);
 
-    if line_string.len() > last_index {
-        highlighted_line.push_span(Span::from(line_string[last_index..].to_string()));
-    }
+    own_spans(highlighter.highlight_line(&line_string))
+}
 
-    highlighted_line
+/// Detaches a `Line`'s spans from the buffer they borrow from, so it can be returned with an
+/// unrelated lifetime.
+pub(crate) fn own_spans<'a>(line: Line<'_>) -> Line<'a> {
+    Line::from(
+        line.spans
+            .into_iter()
+            .map(|span| Span::from(span.content.into_owned()).style(span.style))
+            .collect::<Vec<_>>(),
+    )
 }
 
-/// Creates `Text` from the given `line` argument and adds `highlight_style` to `Spans` that match the pattern.
+/// Creates `Text` from the given `text` argument and adds `highlight_style` to `Spans` that match the pattern.
 /// When the '\n' character is encountered, a new `Line` begins.
 ///
+/// The pattern is matched against the whole text in a single pass rather than line by line,
+/// so it can match across line boundaries (e.g. a `(?s)`-flagged pattern spanning a fenced
+/// code block); a match that spans several lines is broken into one styled span per line it
+/// touches.
+///
 /// # Arguments
 ///
 /// * `text` - A string that holds the text to be highlighted.
@@ -105,31 +123,18 @@ where
 pub fn highlight_text<'a, S, T>(text: S, pattern: T, highlight_style: Style) -> Text<'a>
 where
     S: Into<String>,
-    T: IntoRegexRef + Clone,
+    T: IntoRegexRef,
 {
     let text_string = text.into();
-    let mut highlighted_text = Text::default();
-
-    let mut last_index = 0;
-
-    for (i, _) in text_string.match_indices('\n') {
-        highlighted_text.push_line(highlight_line(
-            text_string[last_index..i].to_string(),
-            pattern.clone(),
-            highlight_style,
-        ));
-        last_index = i + 1;
-    }
+    let highlighter = Highlighter::from_rules(vec![(pattern.into_regex_ref().into_owned(), highlight_style)]);
 
-    if text_string.len() > last_index {
-        highlighted_text.push_line(highlight_line(
-            text_string[last_index..].to_string(),
-            pattern,
-            highlight_style,
-        ));
-    }
+    own_text(highlighter.highlight_text(&text_string))
+}
 
-    highlighted_text
+/// Detaches a `Text`'s spans from the buffer they borrow from, so it can be returned with an
+/// unrelated lifetime.
+pub(crate) fn own_text<'a>(text: Text<'_>) -> Text<'a> {
+    Text::from(text.lines.into_iter().map(own_spans).collect::<Vec<_>>())
 }
 
 pub trait IntoRegexRef {
@@ -219,4 +224,19 @@ mod tests {
         assert_eq!(returned_text, text);
         assert_eq!(returned_text_reg, text);
     }
+
+    #[test]
+    fn highlighting_text_matches_across_newlines() {
+        let returned_text = highlight_text("before\n```\ncode\n```\nafter", r"(?s)```.*?```", STYLE);
+
+        let text = Text::from(vec![
+            Line::from(vec![Span::from("before")]),
+            Line::from(vec![Span::from("```").style(STYLE)]),
+            Line::from(vec![Span::from("code").style(STYLE)]),
+            Line::from(vec![Span::from("```").style(STYLE)]),
+            Line::from(vec![Span::from("after")]),
+        ]);
+
+        assert_eq!(returned_text, text);
+    }
 }
@@ -0,0 +1,142 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::{safe_slice, IntoRegexRef};
+
+/// Creates a `Line` from the given `line` argument, giving each distinct matched substring
+/// its own stable color instead of a single shared `Style`.
+///
+/// The color is derived from an FNV-1a hash of the matched text mapped to a hue, so the same
+/// match (e.g. the same `@buddy` mention) is always colored the same way, both within a line
+/// and across calls.
+///
+/// # Arguments
+///
+/// * `line` - A string that holds the line of text to be highlighted.
+/// * `pattern` - A regular expression pattern to match the text that needs to be highlighted.
+/// * `base_style` - The style applied to every match, with its foreground color replaced by
+///   the one derived from the matched text.
+///
+/// # Example
+///
+/// ```
+/// use tui_pattern_highlighter::highlight_line_rainbow;
+///
+/// let line = highlight_line_rainbow("Hi @buddy, bye @buddy", r"@\w+", ratatui::style::Style::new());
+///
+/// assert_eq!(line.spans[1].content, line.spans[3].content);
+/// assert_eq!(line.spans[1].style, line.spans[3].style);
+/// ```
+///
+/// # Panics
+///
+/// The function may panic if the provided pattern is an invalid regular expression.
+pub fn highlight_line_rainbow<'a, S, T>(line: S, pattern: T, base_style: Style) -> Line<'a>
+where
+    S: Into<String>,
+    T: IntoRegexRef,
+{
+    let line_string = line.into();
+    let regex_ref = pattern.into_regex_ref();
+
+    let mut highlighted_line = Line::default();
+    let mut last_index = 0;
+
+    for m in regex_ref.find_iter(&line_string).collect::<Vec<_>>() {
+        if m.start() > last_index {
+            highlighted_line.push_span(Span::from(safe_slice(&line_string, last_index..m.start()).to_string()));
+        }
+        highlighted_line
+            .push_span(Span::from(m.as_str().to_string()).style(base_style.fg(color_for(m.as_str()))));
+        last_index = m.end();
+    }
+
+    if line_string.len() > last_index {
+        highlighted_line.push_span(Span::from(safe_slice(&line_string, last_index..line_string.len()).to_string()));
+    }
+
+    highlighted_line
+}
+
+/// Maps `text` to a stable `Color` by hashing its bytes into a hue, at a fixed
+/// saturation/lightness of 60%/65%.
+fn color_for(text: &str) -> Color {
+    let hue = (fnv1a(text.as_bytes()) % 360) as f64;
+    let (r, g, b) = hsl_to_rgb(hue, 0.6, 0.65);
+    Color::Rgb(r, g, b)
+}
+
+/// A small FNV-1a hash, good enough to deterministically spread matched strings across hues.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+}
+
+/// Converts an HSL color to an `(r, g, b)` triple in `0..=255`.
+///
+/// # Arguments
+///
+/// * `hue` - The hue, in degrees (`0.0..360.0`).
+/// * `saturation` - The saturation, in `0.0..=1.0`.
+/// * `lightness` - The lightness, in `0.0..=1.0`.
+pub fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    if saturation == 0.0 {
+        let value = (lightness * 255.0).round() as u8;
+        return (value, value, value);
+    }
+
+    let c = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h_prime = hue / 60.0;
+    let x = c * (1.0 - (h_prime.rem_euclid(2.0) - 1.0).abs());
+    let m = lightness - c / 2.0;
+
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    (
+        ((r1 + m) * 255.0).round() as u8,
+        ((g1 + m) * 255.0).round() as u8,
+        ((b1 + m) * 255.0).round() as u8,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_match_gets_the_same_color() {
+        let line = highlight_line_rainbow("Hi @buddy, bye @buddy", r"@\w+", Style::new());
+
+        assert_eq!(line.spans[1].content, "@buddy");
+        assert_eq!(line.spans[3].content, "@buddy");
+        assert_eq!(line.spans[1].style, line.spans[3].style);
+    }
+
+    #[test]
+    fn different_matches_get_different_colors() {
+        let line = highlight_line_rainbow("Hi @buddy, bye @stranger", r"@\w+", Style::new());
+
+        assert_ne!(line.spans[1].style, line.spans[3].style);
+    }
+
+    #[test]
+    fn hsl_to_rgb_handles_primary_hues() {
+        assert_eq!(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+        assert_eq!(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+        assert_eq!(hsl_to_rgb(240.0, 1.0, 0.5), (0, 0, 255));
+        assert_eq!(hsl_to_rgb(0.0, 0.0, 1.0), (255, 255, 255));
+    }
+}